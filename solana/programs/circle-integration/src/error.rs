@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum CircleIntegrationError {
+    /// Specified key does not equal the implementation pubkey encoded in the governance VAA.
+    #[msg("ImplementationMismatch")]
+    ImplementationMismatch,
+
+    /// Governance decree targets a different Wormhole chain than this deployment's.
+    #[msg("GovernanceForAnotherChain")]
+    GovernanceForAnotherChain,
+
+    /// VAA does not decode to a governance action this program recognizes.
+    #[msg("InvalidGovernanceAction")]
+    InvalidGovernanceAction,
+
+    /// The new-authority account does not match the pubkey encoded in the decree, or one was
+    /// provided for a decree that relinquishes the authority instead.
+    #[msg("NewAuthorityMismatch")]
+    NewAuthorityMismatch,
+
+    /// An update-owner decree encoded the default (all-zero) pubkey as the new owner, which would
+    /// permanently strip the custodian of a governance owner with no recovery path.
+    #[msg("InvalidNewOwner")]
+    InvalidNewOwner,
+
+    /// Could not deserialize an account as `UpgradeableLoaderState::ProgramData`.
+    #[msg("InvalidProgramData")]
+    InvalidProgramData,
+
+    /// This program's executable has already been made immutable, so it has no upgrade authority
+    /// left to change.
+    #[msg("ProgramDataImmutable")]
+    ProgramDataImmutable,
+
+    /// The upgrade authority PDA does not match the authority recorded in program data.
+    #[msg("ProgramDataAuthorityMismatch")]
+    ProgramDataAuthorityMismatch,
+
+    /// Foreign chain is unset or is this program's own Wormhole chain ID, neither of which can be
+    /// a registered emitter.
+    #[msg("ChainNotAllowed")]
+    ChainNotAllowed,
+
+    /// An emitter is already registered for this chain and the decree did not set its update flag.
+    #[msg("EmitterAlreadyRegistered")]
+    EmitterAlreadyRegistered,
+
+    /// Could not deserialize the deploy buffer account as `UpgradeableLoaderState::Buffer`.
+    #[msg("InvalidBufferData")]
+    InvalidBufferData,
+
+    /// The deploy buffer's authority is not this program's upgrade authority PDA.
+    #[msg("BufferAuthorityMismatch")]
+    BufferAuthorityMismatch,
+
+    /// The deploy buffer has no program image data.
+    #[msg("BufferDataEmpty")]
+    BufferDataEmpty,
+
+    /// The deploy buffer's program image is smaller than the currently deployed program.
+    #[msg("BufferDataTooSmall")]
+    BufferDataTooSmall,
+
+    /// An account required by this decree was not provided.
+    #[msg("AccountNotProvided")]
+    AccountNotProvided,
+
+    /// The provided `registered_emitter` account does not match the PDA derived for the decree's
+    /// foreign chain.
+    #[msg("RegisteredEmitterMismatch")]
+    RegisteredEmitterMismatch,
+}
@@ -0,0 +1,180 @@
+mod register_emitter_and_domain;
+mod set_upgrade_authority;
+mod update_owner;
+mod upgrade_contract;
+
+use crate::{
+    constants::UPGRADE_SEED_PREFIX,
+    error::CircleIntegrationError,
+    state::{ConsumedVaa, Custodian},
+};
+use anchor_lang::prelude::*;
+use wormhole_cctp_solana::wormhole::{core_bridge_program, VaaAccount, SOLANA_CHAIN};
+use wormhole_solana_utils::cpi::bpf_loader_upgradeable::{self, BpfLoaderUpgradeable};
+
+/// Superset of accounts needed to service any governance decree this program supports. Centering
+/// replay protection, emitter/chain verification, and action-target-chain checks on one context
+/// means a new decree type costs a new match arm and processor module, not a duplicated
+/// `#[derive(Accounts)]` struct.
+#[derive(Accounts)]
+pub struct GovernanceDecree<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Custodian::SEED_PREFIX],
+        bump = custodian.bump,
+    )]
+    custodian: Account<'info, Custodian>,
+
+    /// CHECK: Posted VAA account, which will be read via zero-copy deserialization in the
+    /// instruction handler, which also checks this account discriminator (so there is no need to
+    /// check PDA seeds here).
+    #[account(owner = core_bridge_program::id())]
+    vaa: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ConsumedVaa::INIT_SPACE,
+        seeds = [
+            ConsumedVaa::SEED_PREFIX,
+            VaaAccount::load(&vaa)?.digest().as_ref(),
+        ],
+        bump,
+    )]
+    consumed_vaa: Account<'info, ConsumedVaa>,
+
+    /// CHECK: Upgrade authority PDA. Read for `ContractUpgrade` and `SetUpgradeAuthority`
+    /// decrees.
+    #[account(
+        seeds = [UPGRADE_SEED_PREFIX],
+        bump = custodian.upgrade_authority_bump,
+    )]
+    upgrade_authority: AccountInfo<'info>,
+
+    /// CHECK: New upgrade authority. Only present for a `SetUpgradeAuthority` decree that rotates,
+    /// rather than relinquishes, the authority.
+    new_authority: Option<AccountInfo<'info>>,
+
+    /// CHECK: Deploy buffer. Only present for `ContractUpgrade` decrees.
+    #[account(mut)]
+    buffer: Option<AccountInfo<'info>>,
+
+    /// CHECK: Lamport spill destination. Only present for `ContractUpgrade` decrees.
+    #[account(mut)]
+    spill: Option<AccountInfo<'info>>,
+
+    /// CHECK: This program's program-data account, needed by the BPF Loader Upgradeable program
+    /// for `ContractUpgrade` and `SetUpgradeAuthority` decrees.
+    #[account(
+        mut,
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+    )]
+    program_data: Option<AccountInfo<'info>>,
+
+    /// CHECK: This program, only read for `ContractUpgrade` decrees. Not optional: an account
+    /// equal to `crate::ID` is Anchor's own sentinel for an omitted `Option<AccountInfo>`, so
+    /// wrapping this field as one would make every real call resolve it to `None`. Every decree
+    /// must still supply it, even ones that never read it.
+    #[account(mut, address = crate::ID)]
+    this_program: AccountInfo<'info>,
+
+    /// CHECK: Registered emitter PDA. Only present for `RegisterEmitterAndDomain` decrees, which
+    /// create or overwrite it by manual CPI rather than an `init`/`init_if_needed` constraint,
+    /// because its seeds depend on the chain encoded in the VAA payload rather than anything this
+    /// shared context can validate up front.
+    #[account(mut)]
+    registered_emitter: Option<AccountInfo<'info>>,
+
+    /// CHECK: BPF Loader Upgradeable program. Only present for `ContractUpgrade` and
+    /// `SetUpgradeAuthority` decrees.
+    bpf_loader_upgradeable_program: Option<Program<'info, BpfLoaderUpgradeable>>,
+
+    /// CHECK: BPF Loader Upgradeable program needs this sysvar. Only present for `ContractUpgrade`
+    /// decrees.
+    #[account(address = solana_program::sysvar::rent::id())]
+    rent: Option<AccountInfo<'info>>,
+
+    /// CHECK: BPF Loader Upgradeable program needs this sysvar. Only present for `ContractUpgrade`
+    /// decrees.
+    #[account(address = solana_program::sysvar::clock::id())]
+    clock: Option<AccountInfo<'info>>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Single validated entry point for every governance decree this program supports. Loads the VAA
+/// once, validates it and consumes its replay-protection PDA, checks that the decree targets this
+/// network, then routes to the processor for the decoded action, mirroring the dispatch pattern
+/// the Wormhole core bridge uses for its own guardian-set, fee, and upgrade decrees. Each
+/// processor receives the fields it needs already decoded, so the VAA is parsed and validated
+/// exactly once per instruction regardless of which decree it carries.
+pub fn execute_governance_decree(mut ctx: Context<GovernanceDecree>) -> Result<()> {
+    ctx.accounts.consumed_vaa.set_inner(ConsumedVaa {
+        bump: ctx.bumps.consumed_vaa,
+    });
+
+    let vaa = VaaAccount::load(&ctx.accounts.vaa)?;
+    let gov_payload = crate::processor::require_valid_governance_vaa(&vaa)?;
+
+    if let Some(decree) = gov_payload.contract_upgrade() {
+        check_governance_chain(decree.chain())?;
+        upgrade_contract::process(&mut ctx, Pubkey::from(decree.implementation()))
+    } else if let Some(decree) = gov_payload.set_upgrade_authority() {
+        check_governance_chain(decree.chain())?;
+        set_upgrade_authority::process(&mut ctx, decree.new_authority().map(Pubkey::from))
+    } else if let Some(decree) = gov_payload.register_emitter_and_domain() {
+        check_governance_chain(decree.chain())?;
+        register_emitter_and_domain::process(
+            &mut ctx,
+            decree.foreign_chain(),
+            decree.cctp_domain(),
+            decree.foreign_emitter(),
+            decree.is_update(),
+        )
+    } else if let Some(decree) = gov_payload.update_owner() {
+        check_governance_chain(decree.chain())?;
+        update_owner::process(&mut ctx, Pubkey::from(decree.new_owner()))
+    } else {
+        Err(error!(CircleIntegrationError::InvalidGovernanceAction))
+    }
+}
+
+/// Confirms a decree's target chain is this deployment's own, the one action-target-chain check
+/// every decree variant needs before its processor runs.
+fn check_governance_chain(chain: u16) -> Result<()> {
+    require_eq!(
+        chain,
+        SOLANA_CHAIN,
+        CircleIntegrationError::GovernanceForAnotherChain
+    );
+
+    Ok(())
+}
+
+fn require_account<'a, 'info>(
+    account: &'a Option<AccountInfo<'info>>,
+) -> Result<&'a AccountInfo<'info>> {
+    account
+        .as_ref()
+        .ok_or(error!(CircleIntegrationError::AccountNotProvided))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_governance_chain_accepts_solana_chain() {
+        assert!(check_governance_chain(SOLANA_CHAIN).is_ok());
+    }
+
+    #[test]
+    fn check_governance_chain_rejects_foreign_chain() {
+        assert!(check_governance_chain(SOLANA_CHAIN + 1).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use super::{require_account, GovernanceDecree};
+use crate::{error::CircleIntegrationError, state::RegisteredEmitter};
+use anchor_lang::{prelude::*, Discriminator};
+use wormhole_cctp_solana::wormhole::SOLANA_CHAIN;
+
+/// Processor for register-emitter-and-domain governance decrees. Registers the trusted
+/// circle-integration emitter address and Circle CCTP domain for a foreign Wormhole chain. The
+/// registered-emitter PDA is created (or, when the decree's update flag is set, overwritten) by
+/// manual CPI rather than an `init`/`init_if_needed` constraint, because its seeds depend on
+/// `foreign_chain`, which the shared dispatcher context cannot know ahead of time. The decree's own
+/// target chain has already been checked by the dispatcher in [`super`].
+pub(super) fn process(
+    ctx: &mut Context<GovernanceDecree>,
+    foreign_chain: u16,
+    cctp_domain: u32,
+    foreign_emitter: [u8; 32],
+    is_update: bool,
+) -> Result<()> {
+    // The foreign chain being registered can be neither unset nor Solana's own chain ID.
+    validate_foreign_chain(foreign_chain)?;
+
+    let registered_emitter = require_account(&ctx.accounts.registered_emitter)?;
+
+    // An existing account already carries its own bump, so we only need to look one up (the
+    // expensive way, via `find_program_address`) the first time this PDA is created.
+    let already_registered = registered_emitter.owner == &crate::ID;
+    let existing_bump = already_registered
+        .then(|| {
+            let data = registered_emitter.try_borrow_data()?;
+            RegisteredEmitter::try_deserialize(&mut &data[..]).map(|account| account.bump)
+        })
+        .transpose()?;
+    let bump = verify_registered_emitter_address(registered_emitter.key(), foreign_chain, existing_bump)?;
+
+    // Re-registering an existing emitter is only allowed when the decree's update flag is set.
+    require!(
+        !already_registered || is_update,
+        CircleIntegrationError::EmitterAlreadyRegistered
+    );
+
+    if !already_registered {
+        let space = 8 + RegisteredEmitter::INIT_SPACE;
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: registered_emitter.to_account_info(),
+                },
+                &[&[
+                    RegisteredEmitter::SEED_PREFIX,
+                    foreign_chain.to_be_bytes().as_ref(),
+                    &[bump],
+                ]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+    }
+
+    let account = RegisteredEmitter {
+        chain: foreign_chain,
+        cctp_domain,
+        address: foreign_emitter,
+        bump,
+    };
+
+    let mut data = registered_emitter.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&RegisteredEmitter::DISCRIMINATOR);
+    account.serialize(&mut &mut data[8..])?;
+
+    Ok(())
+}
+
+/// A registered emitter's foreign chain can be neither unset nor Solana's own chain ID.
+fn validate_foreign_chain(chain: u16) -> Result<()> {
+    require!(chain != 0, CircleIntegrationError::ChainNotAllowed);
+    require!(chain != SOLANA_CHAIN, CircleIntegrationError::ChainNotAllowed);
+
+    Ok(())
+}
+
+/// Confirms `address` is the `RegisteredEmitter` PDA for `foreign_chain` and returns its bump.
+/// When `existing_bump` is `Some` (the account has already been created), the address is checked
+/// cheaply with `create_program_address` against that stored bump. Otherwise the canonical bump is
+/// looked up with `find_program_address`, which is unavoidable the first time this PDA is created
+/// but is only paid once, rather than on every subsequent registration update.
+fn verify_registered_emitter_address(
+    address: Pubkey,
+    foreign_chain: u16,
+    existing_bump: Option<u8>,
+) -> Result<u8> {
+    let seed_prefix = RegisteredEmitter::SEED_PREFIX;
+    let chain_seed = foreign_chain.to_be_bytes();
+
+    let bump = match existing_bump {
+        Some(bump) => {
+            let expected = Pubkey::create_program_address(
+                &[seed_prefix, chain_seed.as_ref(), &[bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(CircleIntegrationError::RegisteredEmitterMismatch))?;
+            require_keys_eq!(
+                address,
+                expected,
+                CircleIntegrationError::RegisteredEmitterMismatch
+            );
+            bump
+        }
+        None => {
+            let (expected, bump) =
+                Pubkey::find_program_address(&[seed_prefix, chain_seed.as_ref()], &crate::ID);
+            require_keys_eq!(
+                address,
+                expected,
+                CircleIntegrationError::RegisteredEmitterMismatch
+            );
+            bump
+        }
+    };
+
+    Ok(bump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_foreign_chain_rejects_zero() {
+        assert!(validate_foreign_chain(0).is_err());
+    }
+
+    #[test]
+    fn validate_foreign_chain_rejects_solana_chain() {
+        assert!(validate_foreign_chain(SOLANA_CHAIN).is_err());
+    }
+
+    #[test]
+    fn validate_foreign_chain_accepts_foreign_chain() {
+        assert!(validate_foreign_chain(SOLANA_CHAIN + 1).is_ok());
+    }
+
+    #[test]
+    fn verify_registered_emitter_address_accepts_fresh_pda() {
+        let foreign_chain: u16 = 2;
+        let (address, expected_bump) = Pubkey::find_program_address(
+            &[RegisteredEmitter::SEED_PREFIX, foreign_chain.to_be_bytes().as_ref()],
+            &crate::ID,
+        );
+
+        let bump = verify_registered_emitter_address(address, foreign_chain, None).unwrap();
+        assert_eq!(bump, expected_bump);
+    }
+
+    #[test]
+    fn verify_registered_emitter_address_rejects_wrong_fresh_address() {
+        let result = verify_registered_emitter_address(Pubkey::new_unique(), 2, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_registered_emitter_address_accepts_existing_bump() {
+        let foreign_chain: u16 = 2;
+        let (address, bump) = Pubkey::find_program_address(
+            &[RegisteredEmitter::SEED_PREFIX, foreign_chain.to_be_bytes().as_ref()],
+            &crate::ID,
+        );
+
+        let verified = verify_registered_emitter_address(address, foreign_chain, Some(bump)).unwrap();
+        assert_eq!(verified, bump);
+    }
+
+    #[test]
+    fn verify_registered_emitter_address_rejects_mismatched_existing_bump() {
+        let foreign_chain: u16 = 2;
+        let (address, bump) = Pubkey::find_program_address(
+            &[RegisteredEmitter::SEED_PREFIX, foreign_chain.to_be_bytes().as_ref()],
+            &crate::ID,
+        );
+
+        // A bump one lower than the canonical one either derives no PDA at all or a different
+        // one, so the address no longer matches what was stored on the account.
+        let result = verify_registered_emitter_address(address, foreign_chain, Some(bump - 1));
+        assert!(result.is_err());
+    }
+}
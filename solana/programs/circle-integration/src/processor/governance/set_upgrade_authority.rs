@@ -0,0 +1,154 @@
+use super::{require_account, GovernanceDecree};
+use crate::{constants::UPGRADE_SEED_PREFIX, error::CircleIntegrationError};
+use anchor_lang::prelude::*;
+use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    program::invoke_signed,
+};
+
+/// Processor for set-upgrade-authority governance decrees. Invokes the BPF Loader Upgradeable
+/// program's `SetAuthority` instruction to either rotate the `upgrade_authority` PDA to
+/// `new_authority`, or, when the decree carries no new authority, relinquish it entirely, making
+/// this program's executable immutable. The decree's target chain has already been checked by the
+/// dispatcher in [`super`].
+pub(super) fn process(
+    ctx: &mut Context<GovernanceDecree>,
+    new_authority: Option<Pubkey>,
+) -> Result<()> {
+    // The new authority account, if provided, must match the pubkey encoded in the decree. If the
+    // decree relinquishes the authority, no new authority account should have been passed in.
+    check_new_authority(
+        new_authority,
+        ctx.accounts.new_authority.as_ref().map(|account| account.key()),
+    )?;
+
+    let program_data = require_account(&ctx.accounts.program_data)?;
+
+    // Confirm that our upgrade authority PDA is indeed the current authority before we attempt to
+    // change it.
+    let data = program_data.try_borrow_data()?;
+    let current_authority = current_upgrade_authority(&data)?;
+    require_keys_eq!(
+        current_authority.ok_or(error!(CircleIntegrationError::ProgramDataImmutable))?,
+        ctx.accounts.upgrade_authority.key(),
+        CircleIntegrationError::ProgramDataAuthorityMismatch
+    );
+    drop(data);
+
+    let bpf_loader_upgradeable_program = ctx
+        .accounts
+        .bpf_loader_upgradeable_program
+        .as_ref()
+        .ok_or(error!(CircleIntegrationError::AccountNotProvided))?;
+
+    let mut account_infos = vec![
+        program_data.to_account_info(),
+        ctx.accounts.upgrade_authority.to_account_info(),
+    ];
+
+    if let Some(new_authority) = &ctx.accounts.new_authority {
+        account_infos.push(new_authority.to_account_info());
+    }
+    account_infos.push(bpf_loader_upgradeable_program.to_account_info());
+
+    invoke_signed(
+        &bpf_loader_upgradeable::set_upgrade_authority(
+            &crate::ID,
+            &ctx.accounts.upgrade_authority.key(),
+            new_authority.as_ref(),
+        ),
+        &account_infos,
+        &[&[
+            UPGRADE_SEED_PREFIX,
+            &[ctx.accounts.custodian.upgrade_authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
+
+/// The new authority account, if provided, must match the pubkey encoded in the decree. If the
+/// decree relinquishes the authority, no new authority account should have been passed in.
+fn check_new_authority(decree_new_authority: Option<Pubkey>, provided: Option<Pubkey>) -> Result<()> {
+    match (decree_new_authority, provided) {
+        (Some(encoded), Some(provided)) => {
+            require_keys_eq!(encoded, provided, CircleIntegrationError::NewAuthorityMismatch)
+        }
+        (None, None) => (),
+        _ => return Err(error!(CircleIntegrationError::NewAuthorityMismatch)),
+    };
+
+    Ok(())
+}
+
+/// Reads the current upgrade authority (or `None` if the program has been made immutable) out of
+/// a deserialized `program_data` account.
+fn current_upgrade_authority(program_data: &[u8]) -> Result<Option<Pubkey>> {
+    match bincode::deserialize(program_data)
+        .map_err(|_| error!(CircleIntegrationError::InvalidProgramData))?
+    {
+        UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        } => Ok(upgrade_authority_address),
+        _ => Err(error!(CircleIntegrationError::InvalidProgramData)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_data_bytes(upgrade_authority_address: Option<Pubkey>) -> Vec<u8> {
+        bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn check_new_authority_allows_matching_rotation() {
+        let key = Pubkey::new_unique();
+        assert!(check_new_authority(Some(key), Some(key)).is_ok());
+    }
+
+    #[test]
+    fn check_new_authority_allows_relinquish() {
+        assert!(check_new_authority(None, None).is_ok());
+    }
+
+    #[test]
+    fn check_new_authority_rejects_mismatched_rotation() {
+        let result = check_new_authority(Some(Pubkey::new_unique()), Some(Pubkey::new_unique()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn check_new_authority_rejects_unexpected_account() {
+        assert!(check_new_authority(None, Some(Pubkey::new_unique())).is_err());
+    }
+
+    #[test]
+    fn check_new_authority_rejects_missing_account() {
+        assert!(check_new_authority(Some(Pubkey::new_unique()), None).is_err());
+    }
+
+    #[test]
+    fn current_upgrade_authority_reads_mutable_program() {
+        let key = Pubkey::new_unique();
+        let data = program_data_bytes(Some(key));
+        assert_eq!(current_upgrade_authority(&data).unwrap(), Some(key));
+    }
+
+    #[test]
+    fn current_upgrade_authority_reads_immutable_program() {
+        let data = program_data_bytes(None);
+        assert_eq!(current_upgrade_authority(&data).unwrap(), None);
+    }
+
+    #[test]
+    fn current_upgrade_authority_rejects_malformed_data() {
+        assert!(current_upgrade_authority(&[0xffu8; 4]).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use super::GovernanceDecree;
+use crate::error::CircleIntegrationError;
+use anchor_lang::prelude::*;
+
+/// Processor for owner/governance-rotation decrees, transferring the custodian's governance owner
+/// to `new_owner`. The decree's target chain has already been checked by the dispatcher in
+/// [`super`].
+pub(super) fn process(ctx: &mut Context<GovernanceDecree>, new_owner: Pubkey) -> Result<()> {
+    validate_new_owner(new_owner)?;
+
+    ctx.accounts.custodian.owner = new_owner;
+
+    Ok(())
+}
+
+/// The new owner can't be the default (all-zero) pubkey, which would permanently strip the
+/// custodian of a governance owner with no recovery path.
+fn validate_new_owner(new_owner: Pubkey) -> Result<()> {
+    require_keys_neq!(
+        new_owner,
+        Pubkey::default(),
+        CircleIntegrationError::InvalidNewOwner
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_new_owner_accepts_nonzero_pubkey() {
+        assert!(validate_new_owner(Pubkey::new_unique()).is_ok());
+    }
+
+    #[test]
+    fn validate_new_owner_rejects_default_pubkey() {
+        assert!(validate_new_owner(Pubkey::default()).is_err());
+    }
+}
@@ -1,110 +1,55 @@
-use crate::{
-    constants::UPGRADE_SEED_PREFIX,
-    error::CircleIntegrationError,
-    state::{ConsumedVaa, Custodian},
-};
+use super::{require_account, GovernanceDecree};
+use crate::{constants::UPGRADE_SEED_PREFIX, error::CircleIntegrationError};
 use anchor_lang::prelude::*;
-use wormhole_cctp_solana::wormhole::{core_bridge_program, VaaAccount, SOLANA_CHAIN};
-use wormhole_solana_utils::cpi::bpf_loader_upgradeable::{self, BpfLoaderUpgradeable};
-
-#[derive(Accounts)]
-pub struct UpgradeContract<'info> {
-    #[account(mut)]
-    payer: Signer<'info>,
-
-    #[account(
-        seeds = [Custodian::SEED_PREFIX],
-        bump = custodian.bump,
-    )]
-    custodian: Account<'info, Custodian>,
-
-    /// CHECK: Posted VAA account, which will be read via zero-copy deserialization in the
-    /// instruction handler, which also checks this account discriminator (so there is no need to
-    /// check PDA seeds here).
-    #[account(owner = core_bridge_program::id())]
-    vaa: AccountInfo<'info>,
-
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + ConsumedVaa::INIT_SPACE,
-        seeds = [
-            ConsumedVaa::SEED_PREFIX,
-            VaaAccount::load(&vaa)?.digest().as_ref(),
-        ],
-        bump,
-    )]
-    consumed_vaa: Account<'info, ConsumedVaa>,
-
-    /// CHECK: We need this upgrade authority to invoke the BPF Loader Upgradeable program to
-    /// upgrade this program's executable. We verify this PDA address here out of convenience to get
-    /// the PDA bump seed to invoke the upgrade.
-    #[account(
-        seeds = [UPGRADE_SEED_PREFIX],
-        bump = custodian.upgrade_authority_bump,
-    )]
-    upgrade_authority: AccountInfo<'info>,
-
-    /// CHECK: This account receives any lamports after the result of the upgrade.
-    #[account(mut)]
-    spill: AccountInfo<'info>,
-
-    /// CHECK: Deployed implementation. The pubkey of this account is checked in access control
-    /// against the one encoded in the governance VAA.
-    #[account(mut)]
-    buffer: AccountInfo<'info>,
-
-    /// CHECK: Token Bridge program data needed for BPF Loader Upgradable program.
-    #[account(
-        mut,
-        seeds = [crate::ID.as_ref()],
-        bump,
-        seeds::program = bpf_loader_upgradeable::id(),
-    )]
-    program_data: AccountInfo<'info>,
-
-    /// CHECK: This must equal the Token Bridge program ID for the BPF Loader Upgradeable program.
-    #[account(
-        mut,
-        address = crate::ID
-    )]
-    this_program: AccountInfo<'info>,
-
-    /// CHECK: BPF Loader Upgradeable program needs this sysvar.
-    #[account(address = solana_program::sysvar::rent::id())]
-    rent: AccountInfo<'info>,
-
-    /// CHECK: BPF Loader Upgradeable program needs this sysvar.
-    #[account(address = solana_program::sysvar::clock::id())]
-    clock: AccountInfo<'info>,
-
-    /// CHECK: BPF Loader Upgradeable program.
-    bpf_loader_upgradeable_program: Program<'info, BpfLoaderUpgradeable>,
-
-    system_program: Program<'info, System>,
-}
+use solana_program::bpf_loader_upgradeable::UpgradeableLoaderState;
+use wormhole_solana_utils::cpi::bpf_loader_upgradeable;
+
+/// Processor for contract-upgrade governance decrees. Invokes the BPF Loader Upgradeable program
+/// to upgrade this program's executable to the `implementation` buffer named in the decree. The
+/// decree's target chain has already been checked by the dispatcher in [`super`].
+pub(super) fn process(ctx: &mut Context<GovernanceDecree>, implementation: Pubkey) -> Result<()> {
+    let buffer = require_account(&ctx.accounts.buffer)?;
+    let spill = require_account(&ctx.accounts.spill)?;
+    let rent = require_account(&ctx.accounts.rent)?;
+    let clock = require_account(&ctx.accounts.clock)?;
+    let program_data = require_account(&ctx.accounts.program_data)?;
+    let bpf_loader_upgradeable_program = ctx
+        .accounts
+        .bpf_loader_upgradeable_program
+        .as_ref()
+        .ok_or(error!(CircleIntegrationError::AccountNotProvided))?;
+
+    // Check the implementation pubkey against the buffer in our account context.
+    require_keys_eq!(
+        implementation,
+        buffer.key(),
+        CircleIntegrationError::ImplementationMismatch
+    );
 
-/// Processor for contract upgrade governance decrees. This instruction handler invokes the BPF
-/// Loader Upgradeable program to upgrade this program's executable to the provided buffer.
-#[access_control(handle_access_control(&ctx))]
-pub fn upgrade_contract(ctx: Context<UpgradeContract>) -> Result<()> {
-    ctx.accounts.consumed_vaa.set_inner(ConsumedVaa {
-        bump: ctx.bumps.consumed_vaa,
-    });
+    // Read the currently deployed program's length so we can reject a buffer that is too small to
+    // be a legitimate replacement.
+    let program_data_bytes = program_data.try_borrow_data()?;
+    let deployed_len = deployed_program_len(&program_data_bytes)?;
+    drop(program_data_bytes);
+
+    // Deserialize the buffer and make sure it is actually owned by our upgrade authority PDA and
+    // large enough to be a legitimate program image, so a malformed or attacker-funded buffer
+    // fails here instead of deep inside the loader CPI.
+    let buffer_data = buffer.try_borrow_data()?;
+    validate_buffer(&buffer_data, ctx.accounts.upgrade_authority.key(), deployed_len)?;
+    drop(buffer_data);
 
     // Finally upgrade.
     bpf_loader_upgradeable::upgrade(CpiContext::new_with_signer(
-        ctx.accounts
-            .bpf_loader_upgradeable_program
-            .to_account_info(),
+        bpf_loader_upgradeable_program.to_account_info(),
         bpf_loader_upgradeable::Upgrade {
             program: ctx.accounts.this_program.to_account_info(),
-            program_data: ctx.accounts.program_data.to_account_info(),
-            buffer: ctx.accounts.buffer.to_account_info(),
+            program_data: program_data.to_account_info(),
+            buffer: buffer.to_account_info(),
             authority: ctx.accounts.upgrade_authority.to_account_info(),
-            spill: ctx.accounts.spill.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-            clock: ctx.accounts.clock.to_account_info(),
+            spill: spill.to_account_info(),
+            rent: rent.to_account_info(),
+            clock: clock.to_account_info(),
         },
         &[&[
             UPGRADE_SEED_PREFIX,
@@ -113,28 +58,110 @@ pub fn upgrade_contract(ctx: Context<UpgradeContract>) -> Result<()> {
     ))
 }
 
-fn handle_access_control(ctx: &Context<UpgradeContract>) -> Result<()> {
-    let vaa = VaaAccount::load(&ctx.accounts.vaa)?;
-    let gov_payload = crate::processor::require_valid_governance_vaa(&vaa)?;
-
-    let upgrade = gov_payload
-        .contract_upgrade()
-        .ok_or(error!(CircleIntegrationError::InvalidGovernanceAction))?;
+fn deserialize_loader_state(
+    data: &[u8],
+    err: CircleIntegrationError,
+) -> Result<UpgradeableLoaderState> {
+    bincode::deserialize(data).map_err(|_| error!(err))
+}
 
-    // Make sure that the contract upgrade is intended for this network.
-    require_eq!(
-        upgrade.chain(),
-        SOLANA_CHAIN,
-        CircleIntegrationError::GovernanceForAnotherChain
-    );
+/// Reads the currently deployed program's image length out of a deserialized `program_data`
+/// account.
+fn deployed_program_len(program_data: &[u8]) -> Result<usize> {
+    match deserialize_loader_state(program_data, CircleIntegrationError::InvalidProgramData)? {
+        UpgradeableLoaderState::ProgramData { .. } => {
+            Ok(program_data.len() - UpgradeableLoaderState::size_of_programdata_metadata())
+        }
+        _ => Err(error!(CircleIntegrationError::InvalidProgramData)),
+    }
+}
 
-    // Read the implementation pubkey and check against the buffer in our account context.
-    require_keys_eq!(
-        Pubkey::from(upgrade.implementation()),
-        ctx.accounts.buffer.key(),
-        CircleIntegrationError::ImplementationMismatch
+/// Confirms the deploy buffer is owned by `expected_authority` and carries a program image at
+/// least as large as `deployed_len`, so a malformed or attacker-funded buffer fails here instead
+/// of deep inside the loader CPI.
+fn validate_buffer(buffer_data: &[u8], expected_authority: Pubkey, deployed_len: usize) -> Result<()> {
+    match deserialize_loader_state(buffer_data, CircleIntegrationError::InvalidBufferData)? {
+        UpgradeableLoaderState::Buffer { authority_address } => require_keys_eq!(
+            authority_address.ok_or(error!(CircleIntegrationError::BufferAuthorityMismatch))?,
+            expected_authority,
+            CircleIntegrationError::BufferAuthorityMismatch
+        ),
+        _ => return Err(error!(CircleIntegrationError::InvalidBufferData)),
+    };
+
+    let buffer_len = buffer_data.len() - UpgradeableLoaderState::size_of_buffer_metadata();
+    require!(buffer_len != 0, CircleIntegrationError::BufferDataEmpty);
+    require!(
+        buffer_len >= deployed_len,
+        CircleIntegrationError::BufferDataTooSmall
     );
 
-    // Done.
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_data_bytes(data_len: usize) -> Vec<u8> {
+        let mut bytes = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(Pubkey::new_unique()),
+        })
+        .unwrap();
+        bytes.resize(UpgradeableLoaderState::size_of_programdata_metadata() + data_len, 0);
+        bytes
+    }
+
+    fn buffer_bytes(authority_address: Option<Pubkey>, data_len: usize) -> Vec<u8> {
+        let mut bytes =
+            bincode::serialize(&UpgradeableLoaderState::Buffer { authority_address }).unwrap();
+        bytes.resize(UpgradeableLoaderState::size_of_buffer_metadata() + data_len, 0);
+        bytes
+    }
+
+    #[test]
+    fn deployed_program_len_reads_length() {
+        let data = program_data_bytes(100);
+        assert_eq!(deployed_program_len(&data).unwrap(), 100);
+    }
+
+    #[test]
+    fn deployed_program_len_rejects_non_program_data_account() {
+        let data = buffer_bytes(Some(Pubkey::new_unique()), 100);
+        assert!(deployed_program_len(&data).is_err());
+    }
+
+    #[test]
+    fn validate_buffer_accepts_matching_authority_and_sufficient_length() {
+        let authority = Pubkey::new_unique();
+        let data = buffer_bytes(Some(authority), 100);
+        assert!(validate_buffer(&data, authority, 100).is_ok());
+    }
+
+    #[test]
+    fn validate_buffer_rejects_authority_mismatch() {
+        let data = buffer_bytes(Some(Pubkey::new_unique()), 100);
+        assert!(validate_buffer(&data, Pubkey::new_unique(), 100).is_err());
+    }
+
+    #[test]
+    fn validate_buffer_rejects_immutable_buffer() {
+        let data = buffer_bytes(None, 100);
+        assert!(validate_buffer(&data, Pubkey::new_unique(), 100).is_err());
+    }
+
+    #[test]
+    fn validate_buffer_rejects_empty_buffer() {
+        let authority = Pubkey::new_unique();
+        let data = buffer_bytes(Some(authority), 0);
+        assert!(validate_buffer(&data, authority, 0).is_err());
+    }
+
+    #[test]
+    fn validate_buffer_rejects_buffer_smaller_than_deployed_program() {
+        let authority = Pubkey::new_unique();
+        let data = buffer_bytes(Some(authority), 50);
+        assert!(validate_buffer(&data, authority, 100).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Foreign emitter account data, mapping a Wormhole chain ID to its trusted circle-integration
+/// contract address and Circle CCTP domain. Registered exclusively through governance.
+#[account]
+#[derive(InitSpace)]
+pub struct RegisteredEmitter {
+    /// Wormhole chain ID of the foreign circle-integration contract.
+    pub chain: u16,
+
+    /// Circle CCTP domain corresponding to `chain`.
+    pub cctp_domain: u32,
+
+    /// Foreign emitter address.
+    pub address: [u8; 32],
+
+    /// PDA bump.
+    pub bump: u8,
+}
+
+impl RegisteredEmitter {
+    pub const SEED_PREFIX: &'static [u8] = b"registered_emitter";
+}